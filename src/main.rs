@@ -1,23 +1,32 @@
 use askama::Template;
 use askama_axum::IntoResponse as AskamaIntoResponse;
+use async_trait::async_trait;
 use axum::{
-    extract::{Form, Path, State},
-    http::StatusCode,
+    extract::{Form, Path, Query, Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse as AxumIntoResponse, Redirect, Response},
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use chrono::Timelike;
 use chrono::{DateTime, Utc};
-use futures_util::StreamExt;
+use futures_util::{stream, StreamExt};
+use rand::RngCore;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, QueryBuilder, SqlitePool};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
 use tokio::time::{self, Duration};
 use validator::Validate;
 
 enum ApiError {
     SQLError(sqlx::Error),
+    Unauthorized,
 }
 
 impl From<sqlx::Error> for ApiError {
@@ -31,23 +40,526 @@ impl AxumIntoResponse for ApiError {
         match self {
             Self::SQLError(e) => {
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR, 
+                    StatusCode::INTERNAL_SERVER_ERROR,
                     format!("SQL Error: {e}")
                     ).into_response()
             }
+            Self::Unauthorized => {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    "Unauthorized: provide a valid `Authorization: Bearer <token>` header",
+                    ).into_response()
+            }
         }
     }
 }
 
-async fn get_websites(State(state): State<AppState>) -> Result<impl AskamaIntoResponse, ApiError> {
-    let websites = sqlx::query_as::<_, Website>("SELECT url, alias FROM websites")
-        .fetch_all(&state.db)
+/// Backend-agnostic data access for the monitor. Every handler and the
+/// background checker talk to the database through this trait so the
+/// template can run against either a shared Postgres instance on Shuttle
+/// or a local SQLite file without touching the HTTP layer.
+#[async_trait]
+trait Store: Send + Sync {
+    async fn list_websites(&self) -> Result<Vec<Website>, ApiError>;
+    async fn list_monitored_sites(&self) -> Result<Vec<MonitoredSite>, ApiError>;
+    async fn daily_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError>;
+    async fn monthly_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError>;
+    async fn get_website_by_alias(&self, alias: &str) -> Result<Website, ApiError>;
+    async fn incidents(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<Incident>, ApiError>;
+    async fn insert_log(
+        &self,
+        alias: &str,
+        status: i16,
+        response_ms: Option<i32>,
+    ) -> Result<(), ApiError>;
+    async fn insert_website(&self, website: &Website) -> Result<(), ApiError>;
+    async fn delete_website(&self, alias: &str) -> Result<(), ApiError>;
+    async fn count_tokens(&self) -> Result<i64, ApiError>;
+    async fn insert_token(&self, hashed: &str, label: &str) -> Result<(), ApiError>;
+    async fn token_exists(&self, hashed: &str) -> Result<bool, ApiError>;
+}
+
+/// Postgres implementation, used by the Shuttle deployment.
+pub struct PostgresStore {
+    db: PgPool,
+}
+
+impl PostgresStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl Store for PostgresStore {
+    async fn list_websites(&self) -> Result<Vec<Website>, ApiError> {
+        let websites = sqlx::query_as::<_, Website>("SELECT url, alias FROM websites")
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(websites)
+    }
+
+    async fn list_monitored_sites(&self) -> Result<Vec<MonitoredSite>, ApiError> {
+        let sites =
+            sqlx::query_as::<_, MonitoredSite>("SELECT url, alias, interval_secs FROM websites")
+                .fetch_all(&self.db)
+                .await?;
+
+        Ok(sites)
+    }
+
+    async fn daily_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let mut qb = stats_query(
+            alias,
+            filters,
+            "date_trunc('hour', created_at) as time,
+                CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct,
+                CAST(AVG(response_ms) AS int4) as avg_ms,
+                CAST(percentile_cont(0.95) WITHIN GROUP (ORDER BY response_ms) AS int4) as p95_ms",
+            24,
+            None,
+        );
+        let data = qb.build_query_as::<WebsiteStats>().fetch_all(&self.db).await?;
+
+        let number_of_splits = 24;
+        let number_of_seconds = 3600;
+
+        Ok(fill_data_gaps(
+            data,
+            number_of_splits,
+            SplitBy::Hour,
+            number_of_seconds,
+            filters,
+        ))
+    }
+
+    async fn monthly_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let mut qb = stats_query(
+            alias,
+            filters,
+            "date_trunc('day', created_at) as time,
+                CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct,
+                CAST(AVG(response_ms) AS int4) as avg_ms,
+                CAST(percentile_cont(0.95) WITHIN GROUP (ORDER BY response_ms) AS int4) as p95_ms",
+            30,
+            None,
+        );
+        let data = qb.build_query_as::<WebsiteStats>().fetch_all(&self.db).await?;
+
+        let number_of_splits = 30;
+        let number_of_seconds = 86400;
+
+        Ok(fill_data_gaps(
+            data,
+            number_of_splits,
+            SplitBy::Day,
+            number_of_seconds,
+            filters,
+        ))
+    }
+
+    async fn get_website_by_alias(&self, alias: &str) -> Result<Website, ApiError> {
+        let website =
+            sqlx::query_as::<_, Website>("SELECT url, alias FROM websites WHERE alias = $1")
+                .bind(alias)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(website)
+    }
+
+    async fn incidents(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<Incident>, ApiError> {
+        let mut qb = incidents_query(alias, filters, None);
+        let incidents = qb.build_query_as::<Incident>().fetch_all(&self.db).await?;
+
+        Ok(incidents)
+    }
+
+    async fn insert_log(
+        &self,
+        alias: &str,
+        status: i16,
+        response_ms: Option<i32>,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            "INSERT INTO logs (website_id, status, response_ms)
+                    VALUES
+                    ((SELECT id FROM websites where alias = $1), $2, $3)",
+        )
+        .bind(alias)
+        .bind(status)
+        .bind(response_ms)
+        .execute(&self.db)
         .await?;
 
+        Ok(())
+    }
+
+    async fn insert_website(&self, website: &Website) -> Result<(), ApiError> {
+        sqlx::query("INSERT INTO websites (url, alias) VALUES ($1, $2)")
+            .bind(&website.url)
+            .bind(&website.alias)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_website(&self, alias: &str) -> Result<(), ApiError> {
+        let mut tx = self.db.begin().await?;
+        if let Err(e) = sqlx::query(
+            "DELETE FROM logs WHERE website_id = (SELECT id FROM websites WHERE alias = $1)",
+        )
+        .bind(alias)
+        .execute(&mut *tx)
+        .await
+        {
+            tx.rollback().await?;
+            return Err(ApiError::SQLError(e));
+        };
+
+        if let Err(e) = sqlx::query("DELETE FROM websites WHERE alias = $1")
+            .bind(alias)
+            .execute(&mut *tx)
+            .await
+        {
+            tx.rollback().await?;
+            return Err(ApiError::SQLError(e));
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn count_tokens(&self) -> Result<i64, ApiError> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tokens")
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn insert_token(&self, hashed: &str, label: &str) -> Result<(), ApiError> {
+        sqlx::query("INSERT INTO tokens (hashed, label) VALUES ($1, $2)")
+            .bind(hashed)
+            .bind(label)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn token_exists(&self, hashed: &str) -> Result<bool, ApiError> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM tokens WHERE hashed = $1)")
+                .bind(hashed)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(exists)
+    }
+}
+
+/// SQLite implementation, used for running the monitor locally against a
+/// file-backed database. The aggregate queries differ from Postgres
+/// because `date_trunc(...)` is Postgres-specific and becomes `strftime`.
+pub struct SqliteStore {
+    db: SqlitePool,
+}
+
+impl SqliteStore {
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl Store for SqliteStore {
+    async fn list_websites(&self) -> Result<Vec<Website>, ApiError> {
+        let websites = sqlx::query_as::<_, Website>("SELECT url, alias FROM websites")
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(websites)
+    }
+
+    async fn list_monitored_sites(&self) -> Result<Vec<MonitoredSite>, ApiError> {
+        let sites =
+            sqlx::query_as::<_, MonitoredSite>("SELECT url, alias, interval_secs FROM websites")
+                .fetch_all(&self.db)
+                .await?;
+
+        Ok(sites)
+    }
+
+    async fn daily_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let mut qb = stats_query(
+            alias,
+            filters,
+            "strftime('%Y-%m-%d %H:00:00', created_at) as time,
+                CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct,
+                CAST(AVG(response_ms) AS int4) as avg_ms,
+                NULL as p95_ms",
+            24,
+            Some("datetime"),
+        );
+        let data = qb.build_query_as::<WebsiteStats>().fetch_all(&self.db).await?;
+
+        let number_of_splits = 24;
+        let number_of_seconds = 3600;
+
+        Ok(fill_data_gaps(
+            data,
+            number_of_splits,
+            SplitBy::Hour,
+            number_of_seconds,
+            filters,
+        ))
+    }
+
+    async fn monthly_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let mut qb = stats_query(
+            alias,
+            filters,
+            "strftime('%Y-%m-%d 00:00:00', created_at) as time,
+                CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct,
+                CAST(AVG(response_ms) AS int4) as avg_ms,
+                NULL as p95_ms",
+            30,
+            Some("datetime"),
+        );
+        let data = qb.build_query_as::<WebsiteStats>().fetch_all(&self.db).await?;
+
+        let number_of_splits = 30;
+        let number_of_seconds = 86400;
+
+        Ok(fill_data_gaps(
+            data,
+            number_of_splits,
+            SplitBy::Day,
+            number_of_seconds,
+            filters,
+        ))
+    }
+
+    async fn get_website_by_alias(&self, alias: &str) -> Result<Website, ApiError> {
+        let website =
+            sqlx::query_as::<_, Website>("SELECT url, alias FROM websites WHERE alias = $1")
+                .bind(alias)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(website)
+    }
+
+    async fn incidents(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<Incident>, ApiError> {
+        let mut qb = incidents_query(alias, filters, Some("datetime"));
+        let incidents = qb.build_query_as::<Incident>().fetch_all(&self.db).await?;
+
+        Ok(incidents)
+    }
+
+    async fn insert_log(
+        &self,
+        alias: &str,
+        status: i16,
+        response_ms: Option<i32>,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            "INSERT INTO logs (website_id, status, response_ms)
+                    VALUES
+                    ((SELECT id FROM websites where alias = $1), $2, $3)",
+        )
+        .bind(alias)
+        .bind(status)
+        .bind(response_ms)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_website(&self, website: &Website) -> Result<(), ApiError> {
+        sqlx::query("INSERT INTO websites (url, alias) VALUES ($1, $2)")
+            .bind(&website.url)
+            .bind(&website.alias)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_website(&self, alias: &str) -> Result<(), ApiError> {
+        let mut tx = self.db.begin().await?;
+        if let Err(e) = sqlx::query(
+            "DELETE FROM logs WHERE website_id = (SELECT id FROM websites WHERE alias = $1)",
+        )
+        .bind(alias)
+        .execute(&mut *tx)
+        .await
+        {
+            tx.rollback().await?;
+            return Err(ApiError::SQLError(e));
+        };
+
+        if let Err(e) = sqlx::query("DELETE FROM websites WHERE alias = $1")
+            .bind(alias)
+            .execute(&mut *tx)
+            .await
+        {
+            tx.rollback().await?;
+            return Err(ApiError::SQLError(e));
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn count_tokens(&self) -> Result<i64, ApiError> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tokens")
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn insert_token(&self, hashed: &str, label: &str) -> Result<(), ApiError> {
+        sqlx::query("INSERT INTO tokens (hashed, label) VALUES ($1, $2)")
+            .bind(hashed)
+            .bind(label)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn token_exists(&self, hashed: &str) -> Result<bool, ApiError> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM tokens WHERE hashed = $1)")
+                .bind(hashed)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(exists)
+    }
+}
+
+/// Generate a fresh random API token to hand to the operator. Only the
+/// hash is ever stored, so this plaintext is shown exactly once.
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex_encode(&bytes)
+}
+
+/// SHA-256 of a token, stored in the `tokens` table so a leaked database
+/// dump does not leak usable credentials.
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex_encode(&hasher.finalize())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+/// If no tokens exist yet, mint one and print it. This is the `atuin`-style
+/// bootstrap path: the operator copies the token out of the first-run logs
+/// and uses it as a bearer credential for the write endpoints.
+async fn bootstrap_token(store: &Arc<dyn Store>) {
+    let count = match store.count_tokens().await {
+        Ok(count) => count,
+        Err(ApiError::SQLError(e)) => {
+            eprintln!("failed to query existing API tokens: {e}");
+            return;
+        }
+        Err(_) => return,
+    };
+
+    if count == 0 {
+        let token = generate_token();
+        if let Err(ApiError::SQLError(e)) =
+            store.insert_token(&hash_token(&token), "bootstrap").await
+        {
+            eprintln!("failed to store bootstrap API token: {e}");
+            return;
+        }
+        println!("No API tokens found; generated a bootstrap token (shown once): {token}");
+    }
+}
+
+/// Reject write requests that do not carry a known bearer token. Read-only
+/// dashboard routes are registered without this layer and stay public.
+async fn require_token(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+
+    if state.store.token_exists(&hash_token(token)).await? {
+        Ok(next.run(request).await)
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+async fn get_websites(State(state): State<AppState>) -> Result<impl AskamaIntoResponse, ApiError> {
+    let websites = state.store.list_websites().await?;
+
     let mut logs = Vec::new();
 
     for website in websites {
-        let data = get_daily_stats(&website.alias, &state.db).await?;
+        let data = state.daily_stats(&website.alias, &OptFilters::default()).await?;
 
         logs.push(WebsiteInfo {
             url: website.url,
@@ -59,55 +571,6 @@ async fn get_websites(State(state): State<AppState>) -> Result<impl AskamaIntoRe
     Ok(WebsiteLogs { logs })
 }
 
-async fn get_daily_stats(alias: &str, db: &PgPool) -> Result<Vec<WebsiteStats>, ApiError> {
-    let data = sqlx::query_as::<_, WebsiteStats>(
-        r#"
-            SELECT date_trunc('hour', created_at) as time, 
-            CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct 
-            FROM logs 
-            LEFT JOIN websites on websites.id = logs.website_id
-            WHERE websites.alias = $1 
-            group by time
-            order by time asc
-            limit 24
-            "#,
-    )
-    .bind(alias)
-    .fetch_all(db)
-    .await?;
-
-    let number_of_splits = 24;
-    let number_of_seconds = 3600;
-
-    let data = fill_data_gaps(data, number_of_splits, SplitBy::Hour, number_of_seconds);
-
-    Ok(data)
-}
-
-async fn get_monthly_stats(alias: &str, db: &PgPool) -> Result<Vec<WebsiteStats>, ApiError> {
-    let data = sqlx::query_as::<_, WebsiteStats>(
-        r#"
-            SELECT date_trunc('day', created_at) as time, 
-            CAST(COUNT(case when status = 200 then 1 end) * 100 / COUNT(*) AS int2) as uptime_pct 
-            FROM logs 
-            LEFT JOIN websites on websites.id = logs.website_id
-            WHERE websites.alias = $1 
-            group by time
-            order by time asc
-            limit 30
-            "#,
-    )
-    .bind(alias)
-    .fetch_all(db)
-    .await?;
-
-    let number_of_splits = 30;
-    let number_of_seconds = 86400;
-
-    let data = fill_data_gaps(data, number_of_splits, SplitBy::Day, number_of_seconds);
-    Ok(data)
-}
-
 enum SplitBy {
     Hour,
     Day,
@@ -118,10 +581,16 @@ fn fill_data_gaps(
     splits: i32,
     format: SplitBy,
     number_of_seconds: i32,
+    filters: &OptFilters,
 ) -> Vec<WebsiteStats> {
     if (data.len() as i32) < splits {
-        for i in 1..24 {
-            let time = Utc::now() - chrono::Duration::seconds((number_of_seconds * i).into());
+        // Anchor the synthetic buckets to the requested window rather than
+        // always to "now": a historical `before`/`after` range is padded with
+        // empty buckets inside that window, not with the last 24h/30d.
+        let anchor = filters.before.unwrap_or_else(Utc::now);
+
+        for i in 0..splits {
+            let time = anchor - chrono::Duration::seconds((number_of_seconds * i).into());
             let time = time
                 .with_minute(0)
                 .unwrap()
@@ -136,10 +605,17 @@ fn fill_data_gaps(
                 time
             };
 
+            // Do not pad before the start of the requested window.
+            if filters.after.is_some_and(|after| time < after) {
+                continue;
+            }
+
             if !data.iter().any(|x| x.time == time) {
                 data.push(WebsiteStats {
                     time,
                     uptime_pct: None,
+                    avg_ms: None,
+                    p95_ms: None,
                 });
             }
         }
@@ -149,24 +625,175 @@ fn fill_data_gaps(
     data
 }
 
+/// Optional query-string filters for the incident list. Every field is
+/// optional; with none set the query reproduces the old "non-200 logs,
+/// newest first" behaviour so existing callers are unaffected.
+#[derive(Debug, Default, Deserialize)]
+struct OptFilters {
+    before: Option<DateTime<Utc>>,
+    after: Option<DateTime<Utc>>,
+    status: Option<i16>,
+    exclude_status: Option<i16>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    reverse: Option<bool>,
+}
+
+impl OptFilters {
+    /// A cache-key suffix describing the requested stats window. Empty when
+    /// no `before`/`after` bound is set so the default (full-window) entry
+    /// keeps the bare `alias` key the checker invalidates against.
+    fn window_key(&self) -> String {
+        match (self.after, self.before) {
+            (None, None) => String::new(),
+            (after, before) => format!(
+                ":{}:{}",
+                after.map(|t| t.timestamp()).unwrap_or(0),
+                before.map(|t| t.timestamp()).unwrap_or(0),
+            ),
+        }
+    }
+}
+
+/// Push a `created_at` range bound onto a query. `time_fn` wraps both the
+/// column and the bound value in a SQL function so the comparison happens in
+/// one canonical format: Postgres passes `None` (`timestamptz` compares
+/// directly), while SQLite passes `Some("datetime")` because `created_at` is
+/// TEXT in `'%Y-%m-%d %H:%M:%f'` form and a raw lexical comparison against the
+/// RFC3339 value `sqlx` binds would not line up.
+fn push_time_bound<'a, DB>(
+    qb: &mut QueryBuilder<'a, DB>,
+    time_fn: Option<&str>,
+    op: &str,
+    value: DateTime<Utc>,
+) where
+    DB: sqlx::Database,
+    DateTime<Utc>: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    match time_fn {
+        Some(f) => {
+            qb.push(format!(" and {f}(logs.created_at) {op} {f}("));
+            qb.push_bind(value);
+            qb.push(")");
+        }
+        None => {
+            qb.push(format!(" and logs.created_at {op} "));
+            qb.push_bind(value);
+        }
+    }
+}
+
+/// Build a daily/monthly stats query, scoping it to the optional
+/// `before`/`after` window. `select` is the backend-specific projection
+/// (the `date_trunc`/`strftime` bucket plus the aggregate columns), `limit`
+/// the number of buckets to return, and `time_fn` the range-comparison
+/// normaliser (see [`push_time_bound`]). Shared by both `Store`
+/// implementations so the window handling stays identical.
+fn stats_query<'a, DB>(
+    alias: &str,
+    filters: &OptFilters,
+    select: &str,
+    limit: i64,
+    time_fn: Option<&str>,
+) -> QueryBuilder<'a, DB>
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    i64: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    DateTime<Utc>: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    let mut qb: QueryBuilder<'a, DB> = QueryBuilder::new("SELECT ");
+    qb.push(select);
+    qb.push(" FROM logs LEFT JOIN websites on websites.id = logs.website_id WHERE websites.alias = ");
+    qb.push_bind(alias.to_owned());
+
+    if let Some(after) = filters.after {
+        push_time_bound(&mut qb, time_fn, ">=", after);
+    }
+
+    if let Some(before) = filters.before {
+        push_time_bound(&mut qb, time_fn, "<=", before);
+    }
+
+    qb.push(" group by time order by time asc limit ");
+    qb.push_bind(limit);
+
+    qb
+}
+
+/// Build the incident query dynamically from a set of optional filters.
+/// Shared by both backends so the two `Store` implementations stay in sync;
+/// `QueryBuilder` emits the right placeholder syntax for each database.
+fn incidents_query<'a, DB>(
+    alias: &str,
+    filters: &OptFilters,
+    time_fn: Option<&str>,
+) -> QueryBuilder<'a, DB>
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    i16: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    i64: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    DateTime<Utc>: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    let mut qb: QueryBuilder<'a, DB> = QueryBuilder::new(
+        "SELECT logs.created_at as time, logs.status from logs left join websites on websites.id = logs.website_id where websites.alias = ",
+    );
+    qb.push_bind(alias.to_owned());
+
+    match (filters.status, filters.exclude_status) {
+        (Some(status), _) => {
+            qb.push(" and logs.status = ");
+            qb.push_bind(status);
+        }
+        (None, Some(exclude)) => {
+            qb.push(" and logs.status != ");
+            qb.push_bind(exclude);
+        }
+        (None, None) => {
+            qb.push(" and logs.status != 200");
+        }
+    }
+
+    if let Some(after) = filters.after {
+        push_time_bound(&mut qb, time_fn, ">=", after);
+    }
+
+    if let Some(before) = filters.before {
+        push_time_bound(&mut qb, time_fn, "<=", before);
+    }
+
+    qb.push(" order by logs.created_at ");
+    qb.push(if filters.reverse.unwrap_or(false) {
+        "asc"
+    } else {
+        "desc"
+    });
+
+    if let Some(limit) = filters.limit {
+        qb.push(" limit ");
+        qb.push_bind(limit);
+    }
+
+    if let Some(offset) = filters.offset {
+        qb.push(" offset ");
+        qb.push_bind(offset);
+    }
+
+    qb
+}
+
 async fn get_website_by_alias(
     State(state): State<AppState>,
     Path(alias): Path<String>,
+    Query(filters): Query<OptFilters>,
 ) -> Result<impl AskamaIntoResponse, ApiError> {
-    let website = sqlx::query_as::<_, Website>("SELECT url, alias FROM websites WHERE alias = $1")
-        .bind(&alias)
-        .fetch_one(&state.db)
-        .await?;
+    let website = state.store.get_website_by_alias(&alias).await?;
 
-    let last_24_hours_data = get_daily_stats(&website.alias, &state.db).await?;
-    let monthly_data = get_monthly_stats(&website.alias, &state.db).await?;
+    let last_24_hours_data = state.daily_stats(&website.alias, &filters).await?;
+    let monthly_data = state.monthly_stats(&website.alias, &filters).await?;
 
-    let incidents = sqlx::query_as::<_, Incident>(
-        "SELECT logs.created_at as time, logs.status from logs left join websites on websites.id = logs.website_id where websites.alias = $1 and logs.status != 200",
-    )
-    .bind(&alias)
-    .fetch_all(&state.db)
-    .await?;
+    let incidents = state.store.incidents(&alias, &filters).await?;
 
     let log = WebsiteInfo {
         url: website.url,
@@ -181,6 +808,18 @@ async fn get_website_by_alias(
     })
 }
 
+/// JSON endpoint for scoping the incident list to an arbitrary window via
+/// the same `OptFilters` query parameters the dashboard page accepts.
+async fn get_incidents(
+    State(state): State<AppState>,
+    Path(alias): Path<String>,
+    Query(filters): Query<OptFilters>,
+) -> Result<Json<Vec<Incident>>, ApiError> {
+    let incidents = state.store.incidents(&alias, &filters).await?;
+
+    Ok(Json(incidents))
+}
+
 async fn styles() -> impl AxumIntoResponse {
     Response::builder()
         .status(StatusCode::OK)
@@ -200,12 +839,18 @@ async fn create_website(
         ));
     }
 
-    sqlx::query("INSERT INTO websites (url, alias) VALUES ($1, $2)")
-        .bind(new_website.url)
-        .bind(new_website.alias)
-        .execute(&state.db)
-        .await
-        .unwrap();
+    if let Err(e) = state.store.insert_website(&new_website).await {
+        return match e {
+            ApiError::SQLError(sqlx::Error::Database(db)) if db.is_unique_violation() => Err((
+                StatusCode::CONFLICT,
+                "A website with that alias already exists",
+            )),
+            _ => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create website",
+            )),
+        };
+    }
 
     Ok(Redirect::to("/"))
 }
@@ -214,24 +859,7 @@ async fn delete_website(
     State(state): State<AppState>,
     Path(alias): Path<String>,
 ) -> Result<impl AxumIntoResponse, ApiError> {
-    let mut tx = state.db.begin().await?;
-    if let Err(e) = sqlx::query("DELETE FROM logs WHERE website_alias = $1")
-        .bind(&alias)
-        .execute(&mut *tx)
-        .await {
-            tx.rollback().await?;
-            return Err(ApiError::SQLError(e));
-        };
-
-    if let Err(e) = sqlx::query("DELETE FROM websites WHERE alias = $1")
-        .bind(&alias)
-        .execute(&mut *tx)
-        .await {
-            tx.rollback().await?;
-            return Err(ApiError::SQLError(e));
-        }
-
-    tx.commit().await?;
+    state.store.delete_website(&alias).await?;
 
     Ok(StatusCode::OK)
 }
@@ -243,6 +871,16 @@ struct Website {
     alias: String,
 }
 
+/// A website as seen by the background checker, including its per-site poll
+/// cadence. Kept separate from `Website` so the create form stays a plain
+/// `url`/`alias` pair and lets the database default `interval_secs`.
+#[derive(sqlx::FromRow, Clone)]
+struct MonitoredSite {
+    url: String,
+    alias: String,
+    interval_secs: i32,
+}
+
 #[derive(Serialize, sqlx::FromRow, Template)]
 #[template(path = "index.html")]
 struct WebsiteLogs {
@@ -263,10 +901,12 @@ pub struct Incident {
     status: i16,
 }
 
-#[derive(sqlx::FromRow, Serialize)]
+#[derive(sqlx::FromRow, Serialize, Clone)]
 pub struct WebsiteStats {
     time: DateTime<Utc>,
     uptime_pct: Option<i16>,
+    avg_ms: Option<i32>,
+    p95_ms: Option<i32>,
 }
 
 #[derive(Serialize, Validate)]
@@ -277,64 +917,246 @@ struct WebsiteInfo {
     data: Vec<WebsiteStats>,
 }
 
+struct CacheEntry {
+    value: Vec<WebsiteStats>,
+    inserted: Instant,
+}
+
+/// A tiny TTL cache for rendered stats, keyed by website alias. The index
+/// page re-computes the same per-alias aggregates on every request even
+/// though the checker only writes new rows once a minute, so entries live
+/// for `ttl` (~60s, matching the check interval) before a miss falls back
+/// to SQL. Monthly stats are cached under a `"{alias}:monthly"` key.
+#[derive(Clone)]
+struct StatsCache {
+    inner: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    ttl: Duration,
+}
+
+impl StatsCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Option<Vec<WebsiteStats>> {
+        let guard = self.inner.read().await;
+        guard
+            .get(key)
+            .filter(|entry| entry.inserted.elapsed() < self.ttl)
+            .map(|entry| entry.value.clone())
+    }
+
+    async fn insert(&self, key: String, value: Vec<WebsiteStats>) {
+        self.inner.write().await.insert(
+            key,
+            CacheEntry {
+                value,
+                inserted: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop every cached entry for an alias — the daily and monthly entries
+    /// as well as any window-suffixed variants (`{alias}:{window}`) — so the
+    /// next request recomputes them; called right after a new log is written.
+    async fn invalidate(&self, alias: &str) {
+        let prefix = format!("{alias}:");
+        self.inner
+            .write()
+            .await
+            .retain(|key, _| key != alias && !key.starts_with(&prefix));
+    }
+
+    async fn prune(&self) {
+        let ttl = self.ttl;
+        self.inner
+            .write()
+            .await
+            .retain(|_, entry| entry.inserted.elapsed() < ttl);
+    }
+}
+
 #[derive(Clone)]
 struct AppState {
-    db: PgPool,
+    store: Arc<dyn Store>,
+    cache: StatsCache,
 }
 
 impl AppState {
-    fn new(db: PgPool) -> Self {
-        Self { db }
+    fn new(store: Arc<dyn Store>, cache: StatsCache) -> Self {
+        Self { store, cache }
+    }
+
+    async fn daily_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let key = format!("{alias}{}", filters.window_key());
+        if let Some(cached) = self.cache.get(&key).await {
+            return Ok(cached);
+        }
+
+        let data = self.store.daily_stats(alias, filters).await?;
+        self.cache.insert(key, data.clone()).await;
+        Ok(data)
+    }
+
+    async fn monthly_stats(
+        &self,
+        alias: &str,
+        filters: &OptFilters,
+    ) -> Result<Vec<WebsiteStats>, ApiError> {
+        let key = format!("{alias}:monthly{}", filters.window_key());
+        if let Some(cached) = self.cache.get(&key).await {
+            return Ok(cached);
+        }
+
+        let data = self.store.monthly_stats(alias, filters).await?;
+        self.cache.insert(key, data.clone()).await;
+        Ok(data)
     }
 }
 
 #[shuttle_runtime::main]
 async fn main(#[shuttle_shared_db::Postgres] db: PgPool) -> shuttle_axum::ShuttleAxum {
-    sqlx::migrate!().run(&db).await.unwrap();
+    // `DATABASE_URL=sqlite:...` selects the file-backed SQLite backend for all
+    // data access; absent that, fall back to the Shuttle-injected Postgres
+    // pool. The shared Postgres resource in the signature above is still
+    // provisioned either way; with SQLite selected it is simply left unused.
+    let store: Arc<dyn Store> = match std::env::var("DATABASE_URL") {
+        Ok(url) if url.starts_with("sqlite:") => {
+            let pool = SqlitePool::connect(&url).await.unwrap();
+            sqlx::migrate!("./migrations/sqlite")
+                .run(&pool)
+                .await
+                .unwrap();
+            Arc::new(SqliteStore::new(pool))
+        }
+        _ => {
+            sqlx::migrate!("./migrations/postgres")
+                .run(&db)
+                .await
+                .unwrap();
+            Arc::new(PostgresStore::new(db))
+        }
+    };
 
-    let state = AppState::new(db.clone());
+    bootstrap_token(&store).await;
 
+    let cache = StatsCache::new(Duration::from_secs(60));
+
+    let state = AppState::new(store.clone(), cache.clone());
+
+    let checker_cache = cache.clone();
+    tokio::spawn(async move {
+        check_websites(store, checker_cache).await;
+    });
+
+    // Periodically drop expired entries so the cache does not retain stats
+    // for aliases that are no longer being viewed.
     tokio::spawn(async move {
-        check_websites(db).await;
+        let mut interval = time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            cache.prune().await;
+        }
     });
 
+    let protected = Router::new()
+        .route("/websites", post(create_website))
+        .route("/websites/:alias", axum::routing::delete(delete_website))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
     let router = Router::new()
         .route("/", get(get_websites))
-        .route("/websites", post(create_website))
-        .route(
-            "/websites/:alias",
-            get(get_website_by_alias).delete(delete_website),
-        )
+        .route("/websites/:alias", get(get_website_by_alias))
+        .route("/websites/:alias/incidents", get(get_incidents))
         .route("/styles.css", get(styles))
+        .merge(protected)
         .with_state(state);
 
     Ok(router.into())
 }
 
-async fn check_websites(db: PgPool) {
-    let mut interval = time::interval(Duration::from_secs(60));
+/// Maximum number of sites probed at once.
+const CHECK_CONCURRENCY: usize = 16;
+
+async fn check_websites(store: Arc<dyn Store>, cache: StatsCache) {
+    let ctx = Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    // When each alias is next eligible to be checked. A site with no entry
+    // yet is always due, so every site is checked on the first tick.
+    let mut next_due: HashMap<String, Instant> = HashMap::new();
+
+    let mut interval = time::interval(Duration::from_secs(1));
     loop {
         interval.tick().await;
 
-        let ctx = Client::new();
+        let sites = match store.list_monitored_sites().await {
+            Ok(sites) => sites,
+            // A transient DB error must not kill the loop; try again next tick.
+            Err(_) => continue,
+        };
 
-        let mut res = sqlx::query_as::<_, Website>("SELECT url, alias FROM websites").fetch(&db);
+        // Drop scheduling state for aliases that no longer exist so the map
+        // does not grow without bound as sites are added and removed.
+        let current: std::collections::HashSet<&str> =
+            sites.iter().map(|s| s.alias.as_str()).collect();
+        next_due.retain(|alias, _| current.contains(alias.as_str()));
 
-        while let Some(website) = res.next().await {
-            let website = website.unwrap();
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for site in sites {
+            let is_due = next_due.get(&site.alias).map_or(true, |at| now >= *at);
+            if is_due {
+                let cadence = Duration::from_secs(site.interval_secs.max(1) as u64);
+                next_due.insert(site.alias.clone(), now + cadence);
+                due.push(site);
+            }
+        }
 
-            let response = ctx.get(website.url).send().await.unwrap();
+        stream::iter(due)
+            .for_each_concurrent(CHECK_CONCURRENCY, |site| {
+                let ctx = ctx.clone();
+                let store = store.clone();
+                let cache = cache.clone();
+                async move {
+                    check_one(&ctx, &store, &cache, site).await;
+                }
+            })
+            .await;
+    }
+}
 
-            sqlx::query(
-                "INSERT INTO logs (website_id, status)
-                        VALUES
-                        ((SELECT id FROM websites where alias = $1), $2)",
-            )
-            .bind(website.alias)
-            .bind(response.status().as_u16() as i16)
-            .execute(&db)
-            .await
-            .unwrap();
-        }
+/// Probe a single site and persist the result, converting any network error
+/// into a synthetic failure (status 0, null latency) rather than panicking.
+async fn check_one(ctx: &Client, store: &Arc<dyn Store>, cache: &StatsCache, site: MonitoredSite) {
+    let started = Instant::now();
+    let (status, response_ms) = match ctx.get(site.url.as_str()).send().await {
+        Ok(response) => (
+            response.status().as_u16() as i16,
+            Some(started.elapsed().as_millis() as i32),
+        ),
+        Err(_) => (0, None),
+    };
+
+    if store
+        .insert_log(&site.alias, status, response_ms)
+        .await
+        .is_err()
+    {
+        eprintln!("failed to log check for {}", site.alias);
+        return;
     }
+
+    // Fresh data was just written; drop the cached aggregates so the
+    // dashboard reflects it on the next request.
+    cache.invalidate(&site.alias).await;
 }